@@ -2,11 +2,16 @@ use poem_openapi::{Object, Tags};
 
 mod spotify;
 
-pub use spotify::SpotifyApi;
+pub use spotify::{SpotifyApi, TrackDetails};
+
+mod stats;
+
+pub use stats::StatsApi;
 
 #[derive(Tags)]
 enum ApiTags {
     Spotify,
+    Stats,
 }
 
 #[derive(Object)]
@@ -1,18 +1,22 @@
 use poem::{Result, web::Data};
-use poem_openapi::{ApiResponse, Object, OpenApi, payload::Json};
+use poem_openapi::{ApiResponse, Enum, Object, OpenApi, param::Query, payload::Json};
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 
 use super::{ApiTags, ErrorResponse};
-use crate::{AppState, middleware::ApiAuth, models::SpotifyToken};
+use crate::{
+    AppState, ENV_CONFIG, SpotifyClient, SpotifyClientError, SpotifyGateway,
+    middleware::ApiAuth, models::SpotifyToken,
+};
 
-#[derive(Debug, Serialize, Deserialize, Object)]
+#[derive(Debug, Clone, Serialize, Deserialize, Object)]
 pub struct TrackDetails {
     pub item: Option<Track>,
     pub is_playing: bool,
     pub played_at: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Object)]
+#[derive(Debug, Clone, Serialize, Deserialize, Object)]
 pub struct Track {
     pub id: String,
     pub name: String,
@@ -24,7 +28,7 @@ pub struct Track {
     pub popularity: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize, Object)]
+#[derive(Debug, Clone, Serialize, Deserialize, Object)]
 pub struct Album {
     pub id: String,
     pub name: String,
@@ -32,23 +36,177 @@ pub struct Album {
     pub images: Vec<AlbumImage>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Object)]
+#[derive(Debug, Clone, Serialize, Deserialize, Object)]
 pub struct AlbumImage {
     pub url: String,
     pub height: Option<usize>,
     pub width: Option<usize>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Object)]
+#[derive(Debug, Clone, Serialize, Deserialize, Object)]
 pub struct Artist {
     pub id: String,
     pub name: String,
 }
 
+/// Borrowed DTOs mirroring the slice of Spotify's payload shape this crate
+/// cares about. Deserializing straight into these (rather than indexing a
+/// [`serde_json::Value`] field-by-field) lets missing/renamed fields surface
+/// as real parse errors. String fields borrow from the parsed value and are
+/// only copied into owned `String`s when mapped into the public API types.
+#[derive(Deserialize)]
+struct SpotifyArtistDto<'a> {
+    id: &'a str,
+    name: &'a str,
+}
+
+#[derive(Deserialize)]
+struct SpotifyAlbumImageDto<'a> {
+    url: &'a str,
+    height: Option<usize>,
+    width: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct SpotifyAlbumDto<'a> {
+    id: &'a str,
+    name: &'a str,
+    #[serde(borrow)]
+    artists: Vec<SpotifyArtistDto<'a>>,
+    #[serde(borrow)]
+    images: Vec<SpotifyAlbumImageDto<'a>>,
+}
+
+#[derive(Deserialize)]
+struct SpotifyTrackDto<'a> {
+    id: &'a str,
+    name: &'a str,
+    #[serde(borrow)]
+    album: SpotifyAlbumDto<'a>,
+    #[serde(borrow)]
+    artists: Vec<SpotifyArtistDto<'a>>,
+    explicit: bool,
+    preview_url: Option<&'a str>,
+    duration_ms: u64,
+    popularity: u8,
+}
+
+/// Just enough of an item's shape to tell a track apart from an episode or
+/// other unsupported type before committing to a full [`SpotifyTrackDto`]
+/// parse.
+#[derive(Deserialize)]
+struct SpotifyItemTypeDto<'a> {
+    #[serde(rename = "type")]
+    kind: &'a str,
+}
+
+#[derive(Deserialize)]
+struct SpotifyRecentlyPlayedItemDto<'a> {
+    played_at: &'a str,
+    #[serde(borrow)]
+    track: SpotifyTrackDto<'a>,
+}
+
+#[derive(Deserialize)]
+struct SpotifyRecentlyPlayedResponseDto<'a> {
+    #[serde(borrow)]
+    items: Vec<SpotifyRecentlyPlayedItemDto<'a>>,
+}
+
+impl From<SpotifyArtistDto<'_>> for Artist {
+    fn from(dto: SpotifyArtistDto<'_>) -> Self {
+        Artist {
+            id: dto.id.to_string(),
+            name: dto.name.to_string(),
+        }
+    }
+}
+
+impl From<SpotifyAlbumImageDto<'_>> for AlbumImage {
+    fn from(dto: SpotifyAlbumImageDto<'_>) -> Self {
+        AlbumImage {
+            url: dto.url.to_string(),
+            height: dto.height,
+            width: dto.width,
+        }
+    }
+}
+
+impl From<SpotifyAlbumDto<'_>> for Album {
+    fn from(dto: SpotifyAlbumDto<'_>) -> Self {
+        Album {
+            id: dto.id.to_string(),
+            name: dto.name.to_string(),
+            artists: dto.artists.into_iter().map(Artist::from).collect(),
+            images: dto.images.into_iter().map(AlbumImage::from).collect(),
+        }
+    }
+}
+
+impl From<SpotifyTrackDto<'_>> for Track {
+    fn from(dto: SpotifyTrackDto<'_>) -> Self {
+        Track {
+            id: dto.id.to_string(),
+            name: dto.name.to_string(),
+            album: Album::from(dto.album),
+            artists: dto.artists.into_iter().map(Artist::from).collect(),
+            explicit: dto.explicit,
+            preview_url: dto.preview_url.map(|s| s.to_string()),
+            duration_ms: dto.duration_ms,
+            popularity: dto.popularity,
+        }
+    }
+}
+
 #[derive(ApiResponse)]
 enum TrackWidgetResponse {
     #[oai(status = 200)]
-    Ok(Json<TrackDetails>),
+    Ok(
+        Json<TrackDetails>,
+        #[oai(header = "X-Cache-Age-Seconds")] Option<String>,
+    ),
+
+    #[oai(status = 401)]
+    Unauthorized(Json<ErrorResponse>),
+
+    #[oai(status = 500)]
+    InternalServerError(Json<ErrorResponse>),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Enum)]
+#[oai(rename_all = "snake_case")]
+pub enum TimeRange {
+    ShortTerm,
+    MediumTerm,
+    LongTerm,
+}
+
+impl TimeRange {
+    fn as_query_value(&self) -> &'static str {
+        match self {
+            TimeRange::ShortTerm => "short_term",
+            TimeRange::MediumTerm => "medium_term",
+            TimeRange::LongTerm => "long_term",
+        }
+    }
+}
+
+#[derive(ApiResponse)]
+enum TopTracksResponse {
+    #[oai(status = 200)]
+    Ok(Json<Vec<Track>>),
+
+    #[oai(status = 401)]
+    Unauthorized(Json<ErrorResponse>),
+
+    #[oai(status = 500)]
+    InternalServerError(Json<ErrorResponse>),
+}
+
+#[derive(ApiResponse)]
+enum TopArtistsResponse {
+    #[oai(status = 200)]
+    Ok(Json<Vec<Artist>>),
 
     #[oai(status = 401)]
     Unauthorized(Json<ErrorResponse>),
@@ -62,12 +220,58 @@ pub struct SpotifyApi;
 #[OpenApi(tag = "ApiTags::Spotify")]
 impl SpotifyApi {
     const SPOTIFY_API_BASE_URL: &'static str = "https://api.spotify.com/v1";
+    const SPOTIFY_PAGE_SIZE: u32 = 50;
+    const DEFAULT_TOP_ITEMS_LIMIT: u32 = 50;
 
     #[oai(path = "/api/spotify/track-widget", method = "get")]
     async fn get_currently_playing(
         &self,
         state: Data<&AppState>,
         _api_access_key: ApiAuth,
+    ) -> Result<TrackWidgetResponse> {
+        let ttl = Duration::from_secs(ENV_CONFIG.currently_playing_cache_ttl_seconds);
+
+        if let Some((cached, age)) = Self::read_cached_track_details(state.0, ttl).await {
+            return Ok(TrackWidgetResponse::Ok(
+                Json(cached),
+                Some(age.as_secs().to_string()),
+            ));
+        }
+
+        let response = self.fetch_currently_playing_response(state.0).await?;
+
+        if let TrackWidgetResponse::Ok(Json(details), _) = &response {
+            Self::store_cached_track_details(state.0, details.clone()).await;
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the cached [`TrackDetails`] and its age if one exists and is
+    /// younger than `ttl`.
+    async fn read_cached_track_details(
+        state: &AppState,
+        ttl: Duration,
+    ) -> Option<(TrackDetails, Duration)> {
+        let cache = state.currently_playing_cache.read().await;
+        let (cached_at, details) = cache.as_ref()?;
+        let age = cached_at.elapsed();
+
+        if age < ttl {
+            Some((details.clone(), age))
+        } else {
+            None
+        }
+    }
+
+    async fn store_cached_track_details(state: &AppState, details: TrackDetails) {
+        let mut cache = state.currently_playing_cache.write().await;
+        *cache = Some((Instant::now(), details));
+    }
+
+    async fn fetch_currently_playing_response(
+        &self,
+        state: &AppState,
     ) -> Result<TrackWidgetResponse> {
         let token = match SpotifyToken::get_valid_access_token(&*state.db).await {
             Ok(token) => token,
@@ -81,66 +285,122 @@ impl SpotifyApi {
             }
         };
 
-        let http_client = reqwest::Client::new();
-
-        let currently_playing_response = match self
-            .fetch_currently_playing_track(&http_client, &token.access_token)
+        match self
+            .resolve_currently_playing(state.spotify_gateway.as_ref(), &token.access_token)
             .await
         {
-            Ok(response) => response,
+            Ok(details) => Ok(TrackWidgetResponse::Ok(Json(details), None)),
             Err(e) => {
                 eprintln!("Failed to fetch currently playing track: {}", e);
-                return Ok(TrackWidgetResponse::InternalServerError(Json(
+                Ok(TrackWidgetResponse::InternalServerError(Json(
                     ErrorResponse {
                         code: "SPOTIFY_API_ERROR".to_string(),
                         message: "Failed to connect to Spotify API".to_string(),
                         details: None,
                     },
-                )));
+                )))
             }
-        };
+        }
+    }
 
-        match currently_playing_response.status() {
-            reqwest::StatusCode::OK => {
-                match currently_playing_response.json::<serde_json::Value>().await {
-                    Ok(json) => {
-                        let currently_playing = Self::parse_currently_playing_response(&json);
-
-                        // Check if we have a track or if it's an episode/unsupported type
-                        if currently_playing.item.is_some() {
-                            Ok(TrackWidgetResponse::Ok(Json(currently_playing)))
-                        } else {
-                            // Currently playing is an episode or unsupported type, fallback to recently played
-                            self.handle_no_track_playing(&http_client, &token.access_token)
-                                .await
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to parse Spotify response: {}", e);
-                        Ok(TrackWidgetResponse::InternalServerError(Json(
-                            ErrorResponse {
-                                code: "SPOTIFY_RESPONSE_PARSE_FAILURE".to_string(),
-                                message: "Failed to parse Spotify response".to_string(),
-                                details: None,
-                            },
-                        )))
-                    }
+    /// Drives the currently-playing → episode/no-content → recently-played
+    /// fallback purely off a [`SpotifyGateway`], so it can be exercised in
+    /// tests with a fake gateway instead of the live API.
+    async fn resolve_currently_playing(
+        &self,
+        gateway: &dyn SpotifyGateway,
+        access_token: &str,
+    ) -> Result<TrackDetails, SpotifyClientError> {
+        let json = gateway.currently_playing(access_token).await?;
+
+        if json.is_null() {
+            // Nothing currently playing, fallback to recently played
+            return Ok(self.resolve_recently_played(gateway, access_token).await);
+        }
+
+        let currently_playing = Self::parse_currently_playing_response(&json);
+
+        // Check if we have a track or if it's an episode/unsupported type
+        if currently_playing.item.is_some() {
+            Ok(currently_playing)
+        } else {
+            // Currently playing is an episode or unsupported type, fallback to recently played
+            Ok(self.resolve_recently_played(gateway, access_token).await)
+        }
+    }
+
+    /// Falls back to the most recently played track, or an empty payload if
+    /// that also fails (kept non-fatal for a better widget UX).
+    async fn resolve_recently_played(
+        &self,
+        gateway: &dyn SpotifyGateway,
+        access_token: &str,
+    ) -> TrackDetails {
+        match gateway.recently_played(access_token, 1).await {
+            Ok(json) => Self::parse_recently_played_response(&json),
+            Err(e) => {
+                eprintln!("Failed to fetch recently played track: {}", e);
+                TrackDetails {
+                    item: None,
+                    is_playing: false,
+                    played_at: None,
                 }
             }
-            reqwest::StatusCode::NO_CONTENT => {
-                // Nothing currently playing, fallback to recently played
-                self.handle_no_track_playing(&http_client, &token.access_token)
-                    .await
+        }
+    }
+
+    #[oai(path = "/api/spotify/top-tracks", method = "get")]
+    async fn get_top_tracks(
+        &self,
+        state: Data<&AppState>,
+        _api_access_key: ApiAuth,
+        time_range: Query<TimeRange>,
+        limit: Query<Option<u32>>,
+    ) -> Result<TopTracksResponse> {
+        let token = match SpotifyToken::get_valid_access_token(&*state.db).await {
+            Ok(token) => token,
+            Err(e) => {
+                eprintln!("Failed to get valid access token: {}", e);
+                return Ok(TopTracksResponse::Unauthorized(Json(ErrorResponse {
+                    code: "SPOTIFY_AUTH_FAILED".to_string(),
+                    message: "Failed to authenticate with Spotify".to_string(),
+                    details: None,
+                })));
+            }
+        };
+
+        let spotify_client = SpotifyClient::new();
+        let total_limit = limit.0.unwrap_or(Self::DEFAULT_TOP_ITEMS_LIMIT);
+
+        match self
+            .fetch_top_items(
+                &spotify_client,
+                &token.access_token,
+                "tracks",
+                time_range.0,
+                total_limit,
+            )
+            .await
+        {
+            Ok(items) => {
+                let tracks = items
+                    .iter()
+                    .filter_map(|item| match SpotifyTrackDto::deserialize(item) {
+                        Ok(dto) => Some(Track::from(dto)),
+                        Err(e) => {
+                            eprintln!("Failed to parse top track: {}", e);
+                            None
+                        }
+                    })
+                    .collect();
+                Ok(TopTracksResponse::Ok(Json(tracks)))
             }
-            _ => {
-                eprintln!(
-                    "Unexpected response status: {}",
-                    currently_playing_response.status()
-                );
-                Ok(TrackWidgetResponse::InternalServerError(Json(
+            Err(e) => {
+                eprintln!("Failed to fetch top tracks: {}", e);
+                Ok(TopTracksResponse::InternalServerError(Json(
                     ErrorResponse {
-                        code: "SPOTIFY_UNEXPECTED_RESPONSE".to_string(),
-                        message: "Unexpected response from Spotify API".to_string(),
+                        code: "SPOTIFY_API_ERROR".to_string(),
+                        message: "Failed to connect to Spotify API".to_string(),
                         details: None,
                     },
                 )))
@@ -148,84 +408,140 @@ impl SpotifyApi {
         }
     }
 
-    async fn fetch_currently_playing_track(
+    #[oai(path = "/api/spotify/top-artists", method = "get")]
+    async fn get_top_artists(
         &self,
-        http_client: &reqwest::Client,
-        access_token: &str,
-    ) -> Result<reqwest::Response, reqwest::Error> {
-        http_client
-            .get(&format!(
-                "{}/me/player/currently-playing?market=IN",
-                Self::SPOTIFY_API_BASE_URL
-            ))
-            .header("Authorization", format!("Bearer {}", access_token))
-            .send()
-            .await
-    }
+        state: Data<&AppState>,
+        _api_access_key: ApiAuth,
+        time_range: Query<TimeRange>,
+        limit: Query<Option<u32>>,
+    ) -> Result<TopArtistsResponse> {
+        let token = match SpotifyToken::get_valid_access_token(&*state.db).await {
+            Ok(token) => token,
+            Err(e) => {
+                eprintln!("Failed to get valid access token: {}", e);
+                return Ok(TopArtistsResponse::Unauthorized(Json(ErrorResponse {
+                    code: "SPOTIFY_AUTH_FAILED".to_string(),
+                    message: "Failed to authenticate with Spotify".to_string(),
+                    details: None,
+                })));
+            }
+        };
+
+        let spotify_client = SpotifyClient::new();
+        let total_limit = limit.0.unwrap_or(Self::DEFAULT_TOP_ITEMS_LIMIT);
 
-    async fn handle_no_track_playing(
-        &self,
-        http_client: &reqwest::Client,
-        access_token: &str,
-    ) -> Result<TrackWidgetResponse> {
         match self
-            .fetch_recently_played_track(http_client, access_token)
+            .fetch_top_items(
+                &spotify_client,
+                &token.access_token,
+                "artists",
+                time_range.0,
+                total_limit,
+            )
             .await
         {
-            Ok(recently_played) => Ok(TrackWidgetResponse::Ok(Json(recently_played))),
+            Ok(items) => {
+                let artists = items
+                    .iter()
+                    .filter_map(|item| match SpotifyArtistDto::deserialize(item) {
+                        Ok(dto) => Some(Artist::from(dto)),
+                        Err(e) => {
+                            eprintln!("Failed to parse top artist: {}", e);
+                            None
+                        }
+                    })
+                    .collect();
+                Ok(TopArtistsResponse::Ok(Json(artists)))
+            }
             Err(e) => {
-                eprintln!("Failed to fetch recently played track: {}", e);
-                // Return empty response instead of error for better UX
-                Ok(TrackWidgetResponse::Ok(Json(TrackDetails {
-                    item: None,
-                    is_playing: false,
-                    played_at: None,
-                })))
+                eprintln!("Failed to fetch top artists: {}", e);
+                Ok(TopArtistsResponse::InternalServerError(Json(
+                    ErrorResponse {
+                        code: "SPOTIFY_API_ERROR".to_string(),
+                        message: "Failed to connect to Spotify API".to_string(),
+                        details: None,
+                    },
+                )))
             }
         }
     }
 
-    async fn fetch_recently_played_track(
+    /// Pages through `/me/top/{item_type}`, collecting up to `total_limit`
+    /// items. Spotify caps each page at [`Self::SPOTIFY_PAGE_SIZE`], so this
+    /// keeps requesting the next offset until a short page or the requested
+    /// total tells it to stop.
+    async fn fetch_top_items(
         &self,
-        http_client: &reqwest::Client,
+        spotify_client: &SpotifyClient,
         access_token: &str,
-    ) -> Result<TrackDetails, Box<dyn std::error::Error>> {
-        let response = http_client
-            .get(&format!(
-                "{}/me/player/recently-played?limit=1&market=IN",
-                Self::SPOTIFY_API_BASE_URL
-            ))
-            .header("Authorization", format!("Bearer {}", access_token))
-            .send()
-            .await?;
-
-        match response.status() {
-            reqwest::StatusCode::OK => {
-                let json = response.json::<serde_json::Value>().await?;
-                let recently_played = Self::parse_recently_played_response(&json);
-                Ok(recently_played)
+        item_type: &str,
+        time_range: TimeRange,
+        total_limit: u32,
+    ) -> Result<Vec<serde_json::Value>, SpotifyClientError> {
+        let mut items = Vec::new();
+        let mut offset = 0u32;
+
+        while items.len() < total_limit as usize {
+            let page_limit = Self::SPOTIFY_PAGE_SIZE.min(total_limit - items.len() as u32);
+
+            let response = spotify_client
+                .send_with_retry(|| {
+                    spotify_client
+                        .http()
+                        .get(format!(
+                            "{}/me/top/{}?time_range={}&limit={}&offset={}",
+                            Self::SPOTIFY_API_BASE_URL,
+                            item_type,
+                            time_range.as_query_value(),
+                            page_limit,
+                            offset
+                        ))
+                        .header("Authorization", format!("Bearer {}", access_token))
+                })
+                .await?;
+
+            let json = response.json::<serde_json::Value>().await?;
+            let page_items = json["items"].as_array().cloned().unwrap_or_default();
+            let page_len = page_items.len();
+
+            items.extend(page_items);
+            offset += Self::SPOTIFY_PAGE_SIZE;
+
+            if page_len < Self::SPOTIFY_PAGE_SIZE as usize {
+                break;
             }
-            _ => Err(format!(
-                "Failed to fetch recently played tracks: {}",
-                response.status()
-            )
-            .into()),
         }
+
+        Ok(items)
     }
 
     fn parse_currently_playing_response(json: &serde_json::Value) -> TrackDetails {
         let is_playing = json["is_playing"].as_bool().unwrap_or(false);
 
-        let item = if let Some(item_data) = json["item"].as_object() {
-            if item_data["type"].as_str() == Some("track") {
-                Some(Self::parse_track_from_json(item_data))
-            } else {
+        let item = json.get("item").and_then(|item_value| {
+            if item_value.is_null() {
+                return None;
+            }
+
+            match SpotifyItemTypeDto::deserialize(item_value) {
+                Ok(item_type) if item_type.kind == "track" => {
+                    match SpotifyTrackDto::deserialize(item_value) {
+                        Ok(dto) => Some(Track::from(dto)),
+                        Err(e) => {
+                            eprintln!("Failed to parse currently playing track: {}", e);
+                            None
+                        }
+                    }
+                }
                 // Skip non-track items (episodes, podcasts, etc.)
-                None
+                Ok(_) => None,
+                Err(e) => {
+                    eprintln!("Failed to parse currently playing item: {}", e);
+                    None
+                }
             }
-        } else {
-            None
-        };
+        });
 
         TrackDetails {
             item,
@@ -235,77 +551,153 @@ impl SpotifyApi {
     }
 
     fn parse_recently_played_response(json: &serde_json::Value) -> TrackDetails {
-        let empty_vec = vec![];
-        let items = json["items"].as_array().unwrap_or(&empty_vec);
+        let response = match SpotifyRecentlyPlayedResponseDto::deserialize(json) {
+            Ok(response) => response,
+            Err(e) => {
+                eprintln!("Failed to parse recently played response: {}", e);
+                return TrackDetails {
+                    item: None,
+                    is_playing: false,
+                    played_at: None,
+                };
+            }
+        };
 
-        if items.is_empty() {
-            return TrackDetails {
+        match response.items.into_iter().next() {
+            Some(most_recent) => TrackDetails {
+                item: Some(Track::from(most_recent.track)),
+                is_playing: false,
+                played_at: Some(most_recent.played_at.to_string()),
+            },
+            None => TrackDetails {
                 item: None,
                 is_playing: false,
                 played_at: None,
-            };
+            },
         }
+    }
+}
 
-        let most_recent_item = &items[0];
-        let played_at = most_recent_item["played_at"]
-            .as_str()
-            .map(|s| s.to_string());
-
-        let item = most_recent_item["track"]
-            .as_object()
-            .map(|track_data| Self::parse_track_from_json(track_data));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// Returns canned responses once each, so the branching under test can
+    /// be driven without touching the network.
+    struct MockSpotifyGateway {
+        currently_playing: Mutex<Option<Result<serde_json::Value, SpotifyClientError>>>,
+        recently_played: Mutex<Option<Result<serde_json::Value, SpotifyClientError>>>,
+    }
 
-        TrackDetails {
-            item,
-            is_playing: false,
-            played_at,
+    impl MockSpotifyGateway {
+        fn new(
+            currently_playing: Result<serde_json::Value, SpotifyClientError>,
+            recently_played: Result<serde_json::Value, SpotifyClientError>,
+        ) -> Self {
+            Self {
+                currently_playing: Mutex::new(Some(currently_playing)),
+                recently_played: Mutex::new(Some(recently_played)),
+            }
         }
     }
 
-    fn parse_track_from_json(track_data: &serde_json::Map<String, serde_json::Value>) -> Track {
-        Track {
-            id: track_data["id"].as_str().unwrap_or("").to_string(),
-            name: track_data["name"].as_str().unwrap_or("").to_string(),
-            album: Self::parse_album_from_json(&track_data["album"]),
-            artists: Self::parse_artists_from_json(&track_data["artists"]),
-            explicit: track_data["explicit"].as_bool().unwrap_or(false),
-            preview_url: track_data["preview_url"].as_str().map(|s| s.to_string()),
-            duration_ms: track_data["duration_ms"].as_u64().unwrap_or(0),
-            popularity: track_data["popularity"].as_u64().unwrap_or(0) as u8,
+    #[async_trait]
+    impl SpotifyGateway for MockSpotifyGateway {
+        async fn currently_playing(
+            &self,
+            _access_token: &str,
+        ) -> Result<serde_json::Value, SpotifyClientError> {
+            self.currently_playing
+                .lock()
+                .unwrap()
+                .take()
+                .expect("currently_playing called more than once")
         }
-    }
 
-    fn parse_album_from_json(album_data: &serde_json::Value) -> Album {
-        Album {
-            id: album_data["id"].as_str().unwrap_or("").to_string(),
-            name: album_data["name"].as_str().unwrap_or("").to_string(),
-            artists: Self::parse_artists_from_json(&album_data["artists"]),
-            images: Self::parse_album_images_from_json(&album_data["images"]),
+        async fn recently_played(
+            &self,
+            _access_token: &str,
+            _limit: u32,
+        ) -> Result<serde_json::Value, SpotifyClientError> {
+            self.recently_played
+                .lock()
+                .unwrap()
+                .take()
+                .expect("recently_played called more than once")
         }
     }
 
-    fn parse_artists_from_json(artists_value: &serde_json::Value) -> Vec<Artist> {
-        artists_value
-            .as_array()
-            .unwrap_or(&vec![])
-            .iter()
-            .map(|artist| Artist {
-                id: artist["id"].as_str().unwrap_or("").to_string(),
-                name: artist["name"].as_str().unwrap_or("").to_string(),
-            })
-            .collect()
+    fn sample_track_json(id: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "name": "Test Track",
+            "type": "track",
+            "album": { "id": "album-1", "name": "Test Album", "artists": [], "images": [] },
+            "artists": [{ "id": "artist-1", "name": "Test Artist" }],
+            "explicit": false,
+            "preview_url": null,
+            "duration_ms": 1000,
+            "popularity": 50,
+        })
     }
 
-    fn parse_album_images_from_json(images_value: &serde_json::Value) -> Vec<AlbumImage> {
-        images_value
-            .as_array()
-            .unwrap_or(&vec![])
-            .iter()
-            .map(|image| AlbumImage {
-                url: image["url"].as_str().unwrap_or("").to_string(),
-                height: image["height"].as_u64().map(|h| h as usize),
-                width: image["width"].as_u64().map(|w| w as usize),
-            })
-            .collect()
+    #[tokio::test]
+    async fn resolves_track_when_currently_playing() {
+        let gateway = MockSpotifyGateway::new(
+            Ok(json!({ "is_playing": true, "item": sample_track_json("track-1") })),
+            Ok(json!({ "items": [] })),
+        );
+
+        let api = SpotifyApi;
+        let details = api
+            .resolve_currently_playing(&gateway, "token")
+            .await
+            .expect("gateway call should succeed");
+
+        assert!(details.is_playing);
+        assert_eq!(details.item.expect("expected a track").id, "track-1");
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_recently_played_when_nothing_playing() {
+        let gateway = MockSpotifyGateway::new(
+            Ok(serde_json::Value::Null),
+            Ok(json!({
+                "items": [{
+                    "played_at": "2024-01-01T00:00:00Z",
+                    "track": sample_track_json("track-2"),
+                }],
+            })),
+        );
+
+        let api = SpotifyApi;
+        let details = api
+            .resolve_currently_playing(&gateway, "token")
+            .await
+            .expect("gateway call should succeed");
+
+        assert!(!details.is_playing);
+        assert_eq!(details.item.expect("expected a track").id, "track-2");
+        assert_eq!(details.played_at.as_deref(), Some("2024-01-01T00:00:00Z"));
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_empty_when_recently_played_also_fails() {
+        let gateway = MockSpotifyGateway::new(
+            Ok(serde_json::Value::Null),
+            Err(SpotifyClientError::RateLimited),
+        );
+
+        let api = SpotifyApi;
+        let details = api
+            .resolve_currently_playing(&gateway, "token")
+            .await
+            .expect("gateway call should succeed");
+
+        assert!(details.item.is_none());
+        assert!(!details.is_playing);
     }
 }
@@ -0,0 +1,161 @@
+use chrono::{Duration as ChronoDuration, Utc};
+use poem::{Result, web::Data};
+use poem_openapi::{ApiResponse, Object, OpenApi, param::Query, payload::Json};
+
+use super::{ApiTags, ErrorResponse};
+use crate::{
+    AppState,
+    middleware::ApiAuth,
+    models::{ArtistPlayCount, PlayHistory, TrackPlayCount},
+};
+
+#[derive(Debug, Object)]
+pub struct PaginatedHistory {
+    pub items: Vec<PlayHistory>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+#[derive(Debug, Object)]
+pub struct TopFromHistory {
+    pub window: String,
+    pub top_tracks: Vec<TrackPlayCount>,
+    pub top_artists: Vec<ArtistPlayCount>,
+}
+
+#[derive(ApiResponse)]
+enum HistoryResponse {
+    #[oai(status = 200)]
+    Ok(Json<PaginatedHistory>),
+
+    #[oai(status = 500)]
+    InternalServerError(Json<ErrorResponse>),
+}
+
+#[derive(ApiResponse)]
+enum TopFromHistoryResponse {
+    #[oai(status = 200)]
+    Ok(Json<TopFromHistory>),
+
+    #[oai(status = 400)]
+    BadRequest(Json<ErrorResponse>),
+
+    #[oai(status = 500)]
+    InternalServerError(Json<ErrorResponse>),
+}
+
+pub struct StatsApi;
+
+#[OpenApi(tag = "ApiTags::Stats")]
+impl StatsApi {
+    const DEFAULT_HISTORY_LIMIT: i64 = 50;
+    const DEFAULT_WINDOW: &'static str = "30d";
+    const TOP_FROM_HISTORY_LIMIT: i64 = 10;
+
+    /// Paginated listening history, most recent play first, read straight
+    /// from the local `recently_played` table.
+    #[oai(path = "/api/spotify/history", method = "get")]
+    async fn get_history(
+        &self,
+        state: Data<&AppState>,
+        _api_access_key: ApiAuth,
+        limit: Query<Option<i64>>,
+        offset: Query<Option<i64>>,
+    ) -> Result<HistoryResponse> {
+        let limit = limit.0.unwrap_or(Self::DEFAULT_HISTORY_LIMIT);
+        let offset = offset.0.unwrap_or(0);
+
+        match PlayHistory::query_page(&*state.db, limit, offset).await {
+            Ok(items) => Ok(HistoryResponse::Ok(Json(PaginatedHistory {
+                items,
+                limit,
+                offset,
+            }))),
+            Err(e) => {
+                eprintln!("Failed to query play history: {}", e);
+                Ok(HistoryResponse::InternalServerError(Json(ErrorResponse {
+                    code: "HISTORY_QUERY_FAILED".to_string(),
+                    message: "Failed to query listening history".to_string(),
+                    details: None,
+                })))
+            }
+        }
+    }
+
+    /// Top tracks/artists by play count over a trailing `window` (e.g.
+    /// `30d`, `12h`, `2w`), aggregated from locally stored history instead
+    /// of calling Spotify.
+    #[oai(path = "/api/spotify/stats/top-from-history", method = "get")]
+    async fn get_top_from_history(
+        &self,
+        state: Data<&AppState>,
+        _api_access_key: ApiAuth,
+        window: Query<Option<String>>,
+    ) -> Result<TopFromHistoryResponse> {
+        let window = window.0.unwrap_or_else(|| Self::DEFAULT_WINDOW.to_string());
+
+        let Some(duration) = Self::parse_window(&window) else {
+            return Ok(TopFromHistoryResponse::BadRequest(Json(ErrorResponse {
+                code: "INVALID_WINDOW".to_string(),
+                message: format!("Could not parse window '{}'", window),
+                details: None,
+            })));
+        };
+
+        let since = Utc::now().naive_utc() - duration;
+
+        let top_tracks =
+            match PlayHistory::top_tracks_since(&*state.db, since, Self::TOP_FROM_HISTORY_LIMIT)
+                .await
+            {
+                Ok(rows) => rows,
+                Err(e) => {
+                    eprintln!("Failed to aggregate top tracks from history: {}", e);
+                    return Ok(TopFromHistoryResponse::InternalServerError(Json(
+                        ErrorResponse {
+                            code: "HISTORY_QUERY_FAILED".to_string(),
+                            message: "Failed to aggregate listening history".to_string(),
+                            details: None,
+                        },
+                    )));
+                }
+            };
+
+        let top_artists =
+            match PlayHistory::top_artists_since(&*state.db, since, Self::TOP_FROM_HISTORY_LIMIT)
+                .await
+            {
+                Ok(rows) => rows,
+                Err(e) => {
+                    eprintln!("Failed to aggregate top artists from history: {}", e);
+                    return Ok(TopFromHistoryResponse::InternalServerError(Json(
+                        ErrorResponse {
+                            code: "HISTORY_QUERY_FAILED".to_string(),
+                            message: "Failed to aggregate listening history".to_string(),
+                            details: None,
+                        },
+                    )));
+                }
+            };
+
+        Ok(TopFromHistoryResponse::Ok(Json(TopFromHistory {
+            window,
+            top_tracks,
+            top_artists,
+        })))
+    }
+
+    /// Parses windows like `30d`, `12h`, `2w` into a [`ChronoDuration`].
+    fn parse_window(window: &str) -> Option<ChronoDuration> {
+        let unit = window.chars().last()?;
+        let value = &window[..window.len() - unit.len_utf8()];
+        let value: i64 = value.parse().ok()?;
+
+        match unit {
+            'h' => Some(ChronoDuration::hours(value)),
+            'd' => Some(ChronoDuration::days(value)),
+            'w' => Some(ChronoDuration::weeks(value)),
+            _ => None,
+        }
+    }
+}
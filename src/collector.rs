@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+use crate::{
+    AppState, SpotifyClient,
+    models::{PlayHistory, SpotifyToken},
+};
+
+const SPOTIFY_API_BASE_URL: &str = "https://api.spotify.com/v1";
+const POLL_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Spawns a background task that polls `/me/player/recently-played` on an
+/// interval and persists new plays into the `recently_played` table, so
+/// long-term listening stats survive Spotify's own short recently-played
+/// buffer without needing to hit the API on every stats request.
+pub fn spawn_recently_played_collector(state: AppState) {
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = poll_recently_played(&state).await {
+                eprintln!("Failed to poll recently played history: {}", e);
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
+
+async fn poll_recently_played(state: &AppState) -> Result<(), Box<dyn std::error::Error>> {
+    let token = SpotifyToken::get_valid_access_token(&*state.db).await?;
+    let spotify_client = SpotifyClient::new();
+
+    let response = spotify_client
+        .send_with_retry(|| {
+            spotify_client
+                .http()
+                .get(format!(
+                    "{}/me/player/recently-played?limit=50",
+                    SPOTIFY_API_BASE_URL
+                ))
+                .header("Authorization", format!("Bearer {}", token.access_token))
+        })
+        .await?;
+
+    let json = response.json::<serde_json::Value>().await?;
+    let empty_vec = vec![];
+    let items = json["items"].as_array().unwrap_or(&empty_vec);
+
+    for item in items {
+        let Some(played_at) = item["played_at"]
+            .as_str()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc).naive_utc())
+        else {
+            continue;
+        };
+
+        let track = &item["track"];
+        let track_id = track["id"].as_str().unwrap_or("");
+        if track_id.is_empty() {
+            continue;
+        }
+
+        let track_name = track["name"].as_str().unwrap_or("");
+        let album_name = track["album"]["name"].as_str().unwrap_or("");
+        let artist_name = track["artists"]
+            .as_array()
+            .and_then(|artists| artists.first())
+            .and_then(|artist| artist["name"].as_str())
+            .unwrap_or("");
+
+        PlayHistory::insert_if_new(
+            &*state.db,
+            track_id,
+            track_name,
+            artist_name,
+            album_name,
+            played_at,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
@@ -5,8 +5,12 @@ pub struct EnvConfig {
     pub database_url: String,
     pub spotify_client_id: String,
     pub spotify_client_secret: String,
+    pub api_access_key: String,
+    pub currently_playing_cache_ttl_seconds: u64,
 }
 
+const DEFAULT_CURRENTLY_PLAYING_CACHE_TTL_SECONDS: u64 = 10;
+
 pub static ENV_CONFIG: Lazy<EnvConfig> = Lazy::new(|| {
     dotenvy::dotenv().ok();
 
@@ -17,5 +21,12 @@ pub static ENV_CONFIG: Lazy<EnvConfig> = Lazy::new(|| {
 
         spotify_client_secret: env::var("SPOTIFY_CLIENT_SECRET")
             .expect("SPOTIFY_CLIENT_SECRET is not set"),
+
+        api_access_key: env::var("API_ACCESS_KEY").expect("API_ACCESS_KEY is not set"),
+
+        currently_playing_cache_ttl_seconds: env::var("CURRENTLY_PLAYING_CACHE_TTL_SECONDS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_CURRENTLY_PLAYING_CACHE_TTL_SECONDS),
     }
 });
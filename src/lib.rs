@@ -1,5 +1,7 @@
 mod api;
-pub use api::SpotifyApi;
+pub use api::{SpotifyApi, StatsApi};
+
+pub mod middleware;
 
 mod config;
 pub use config::ENV_CONFIG;
@@ -8,3 +10,11 @@ pub mod models;
 
 mod state;
 pub use state::{AppState, create_db_pool};
+
+mod spotify_client;
+pub use spotify_client::{SpotifyClient, SpotifyClientError};
+
+mod spotify_gateway;
+pub use spotify_gateway::{HttpSpotifyGateway, SpotifyGateway};
+
+pub mod collector;
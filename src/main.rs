@@ -1,18 +1,23 @@
 use poem::{EndpointExt, Route, Server, listener::TcpListener, middleware::Cors};
 use poem_openapi::OpenApiService;
 use std::{process::ExitCode, sync::Arc};
+use tokio::sync::RwLock;
 
-use lm04_stats::{AppState, SpotifyApi, create_db_pool};
+use lm04_stats::{AppState, HttpSpotifyGateway, SpotifyApi, StatsApi, collector, create_db_pool};
 
 #[tokio::main]
 async fn main() -> ExitCode {
     let db_pool = create_db_pool().await;
     let state = AppState {
         db: Arc::new(db_pool),
+        currently_playing_cache: Arc::new(RwLock::new(None)),
+        spotify_gateway: Arc::new(HttpSpotifyGateway::new()),
     };
 
-    let api_service =
-        OpenApiService::new(SpotifyApi, "lm04-stats", "1.0").server("http://localhost:3000");
+    collector::spawn_recently_played_collector(state.clone());
+
+    let api_service = OpenApiService::new((SpotifyApi, StatsApi), "lm04-stats", "1.0")
+        .server("http://localhost:3000");
     let ui = api_service.swagger_ui();
 
     let cors = Cors::new().allow_origins_fn(|origin: &str| {
@@ -0,0 +1,5 @@
+mod spotify_token;
+pub use spotify_token::{SpotifyToken, SpotifyTokenError};
+
+mod play_history;
+pub use play_history::{ArtistPlayCount, PlayHistory, TrackPlayCount};
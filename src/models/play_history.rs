@@ -0,0 +1,140 @@
+use chrono::{NaiveDateTime, Utc};
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite};
+
+#[derive(Debug, FromRow, Serialize, Deserialize, Object)]
+pub struct PlayHistory {
+    pub id: i64,
+    pub track_id: String,
+    pub track_name: String,
+    pub artist_name: String,
+    pub album_name: String,
+    pub played_at: NaiveDateTime,
+    pub recorded_at: NaiveDateTime,
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize, Object)]
+pub struct TrackPlayCount {
+    pub track_id: String,
+    pub track_name: String,
+    pub artist_name: String,
+    pub play_count: i64,
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize, Object)]
+pub struct ArtistPlayCount {
+    pub artist_name: String,
+    pub play_count: i64,
+}
+
+impl PlayHistory {
+    /// Inserts a play, deduplicated on `(track_id, played_at)` by the
+    /// `recently_played` table's unique index. Returns whether a new row was
+    /// actually inserted.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn insert_if_new<'e, E>(
+        executor: E,
+        track_id: &str,
+        track_name: &str,
+        artist_name: &str,
+        album_name: &str,
+        played_at: NaiveDateTime,
+    ) -> Result<bool, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite> + Copy,
+    {
+        let recorded_at = Utc::now().naive_utc();
+
+        let result = sqlx::query!(
+            r#"
+                INSERT OR IGNORE INTO recently_played
+                    (track_id, track_name, artist_name, album_name, played_at, recorded_at)
+                VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            track_id,
+            track_name,
+            artist_name,
+            album_name,
+            played_at,
+            recorded_at
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn query_page<'e, E>(
+        executor: E,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite> + Copy,
+    {
+        sqlx::query_as!(
+            PlayHistory,
+            r#"
+                SELECT *
+                FROM recently_played
+                ORDER BY played_at DESC
+                LIMIT $1 OFFSET $2
+            "#,
+            limit,
+            offset
+        )
+        .fetch_all(executor)
+        .await
+    }
+
+    pub async fn top_tracks_since<'e, E>(
+        executor: E,
+        since: NaiveDateTime,
+        limit: i64,
+    ) -> Result<Vec<TrackPlayCount>, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite> + Copy,
+    {
+        sqlx::query_as!(
+            TrackPlayCount,
+            r#"
+                SELECT track_id, track_name, artist_name, COUNT(*) as "play_count!: i64"
+                FROM recently_played
+                WHERE played_at >= $1
+                GROUP BY track_id, track_name, artist_name
+                ORDER BY play_count DESC
+                LIMIT $2
+            "#,
+            since,
+            limit
+        )
+        .fetch_all(executor)
+        .await
+    }
+
+    pub async fn top_artists_since<'e, E>(
+        executor: E,
+        since: NaiveDateTime,
+        limit: i64,
+    ) -> Result<Vec<ArtistPlayCount>, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite> + Copy,
+    {
+        sqlx::query_as!(
+            ArtistPlayCount,
+            r#"
+                SELECT artist_name, COUNT(*) as "play_count!: i64"
+                FROM recently_played
+                WHERE played_at >= $1
+                GROUP BY artist_name
+                ORDER BY play_count DESC
+                LIMIT $2
+            "#,
+            since,
+            limit
+        )
+        .fetch_all(executor)
+        .await
+    }
+}
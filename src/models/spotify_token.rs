@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite};
 use std::{collections::HashMap, fmt};
 
-use crate::ENV_CONFIG;
+use crate::{ENV_CONFIG, SpotifyClient, SpotifyClientError};
 
 #[derive(Debug, FromRow, Serialize, Deserialize, Object)]
 pub struct SpotifyToken {
@@ -71,7 +71,7 @@ impl SpotifyToken {
     where
         E: Executor<'e, Database = Sqlite> + Copy,
     {
-        let http_client = reqwest::Client::new();
+        let spotify_client = SpotifyClient::new();
 
         let mut params = HashMap::new();
         params.insert("grant_type", "refresh_token");
@@ -79,11 +79,14 @@ impl SpotifyToken {
         params.insert("client_id", &ENV_CONFIG.spotify_client_id);
         params.insert("client_secret", &ENV_CONFIG.spotify_client_secret);
 
-        let response = http_client
-            .post(Self::SPOTIFY_TOKEN_URL)
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .form(&params)
-            .send()
+        let response = spotify_client
+            .send_with_retry(|| {
+                spotify_client
+                    .http()
+                    .post(Self::SPOTIFY_TOKEN_URL)
+                    .header("Content-Type", "application/x-www-form-urlencoded")
+                    .form(&params)
+            })
             .await?;
 
         if !response.status().is_success() {
@@ -160,3 +163,17 @@ impl From<reqwest::Error> for SpotifyTokenError {
         SpotifyTokenError::HttpError(err)
     }
 }
+
+impl From<SpotifyClientError> for SpotifyTokenError {
+    fn from(err: SpotifyClientError) -> Self {
+        match err {
+            SpotifyClientError::Http(e) => SpotifyTokenError::HttpError(e),
+            SpotifyClientError::RateLimited => {
+                SpotifyTokenError::RefreshFailed("rate limited by Spotify".to_string())
+            }
+            SpotifyClientError::UnexpectedStatus(status) => {
+                SpotifyTokenError::RefreshFailed(format!("unexpected status {status}"))
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Thin wrapper around [`reqwest::Client`] that routes every Spotify HTTP
+/// call through a shared rate-limit-aware retry loop.
+pub struct SpotifyClient {
+    http: reqwest::Client,
+}
+
+impl SpotifyClient {
+    const MAX_ATTEMPTS: u32 = 5;
+    const BASE_BACKOFF_SECS: u64 = 1;
+    const MAX_BACKOFF_SECS: u64 = 16;
+
+    pub fn new() -> Self {
+        Self {
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    /// Sends a request built by `build_request`, retrying on HTTP 429.
+    ///
+    /// `build_request` is invoked once per attempt since a
+    /// [`reqwest::RequestBuilder`] is consumed by `send`. On a 429 it honors
+    /// the `Retry-After` header when present, otherwise falls back to
+    /// exponential backoff with jitter, up to [`Self::MAX_ATTEMPTS`] tries.
+    pub async fn send_with_retry<F>(
+        &self,
+        mut build_request: F,
+    ) -> Result<reqwest::Response, SpotifyClientError>
+    where
+        F: FnMut() -> reqwest::RequestBuilder,
+    {
+        let mut attempt = 0u32;
+
+        loop {
+            attempt += 1;
+            let response = build_request().send().await?;
+
+            if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+
+            if attempt >= Self::MAX_ATTEMPTS {
+                return Err(SpotifyClientError::RateLimited);
+            }
+
+            tokio::time::sleep(Self::retry_delay(&response, attempt)).await;
+        }
+    }
+
+    fn retry_delay(response: &reqwest::Response, attempt: u32) -> Duration {
+        if let Some(retry_after) = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+        {
+            return Duration::from_secs(retry_after);
+        }
+
+        let backoff_secs = Self::BASE_BACKOFF_SECS
+            .saturating_mul(1 << attempt.saturating_sub(1).min(u32::BITS - 1))
+            .min(Self::MAX_BACKOFF_SECS);
+
+        Duration::from_millis(backoff_secs * 1000 + Self::jitter_millis())
+    }
+
+    /// Small amount of jitter to avoid a thundering herd of retries.
+    fn jitter_millis() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.subsec_millis() as u64 % 250)
+            .unwrap_or(0)
+    }
+}
+
+impl Default for SpotifyClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+pub enum SpotifyClientError {
+    Http(reqwest::Error),
+    RateLimited,
+    UnexpectedStatus(reqwest::StatusCode),
+}
+
+impl fmt::Display for SpotifyClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpotifyClientError::Http(e) => write!(f, "HTTP error: {}", e),
+            SpotifyClientError::RateLimited => {
+                write!(f, "Exceeded retry attempts while rate-limited by Spotify")
+            }
+            SpotifyClientError::UnexpectedStatus(status) => {
+                write!(f, "Unexpected response from Spotify API: {}", status)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpotifyClientError {}
+
+impl From<reqwest::Error> for SpotifyClientError {
+    fn from(err: reqwest::Error) -> Self {
+        SpotifyClientError::Http(err)
+    }
+}
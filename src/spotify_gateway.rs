@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+
+use crate::{SpotifyClient, SpotifyClientError};
+
+const SPOTIFY_API_BASE_URL: &str = "https://api.spotify.com/v1";
+
+/// The Spotify network surface used by the track-widget handlers, pulled
+/// out behind a trait so the fallback branching (currently-playing →
+/// episode/no-content → recently-played → empty) can be driven by a fake
+/// in tests instead of the live API.
+///
+/// A `currently_playing` result of [`serde_json::Value::Null`] represents
+/// Spotify's `204 No Content` (nothing currently playing).
+#[async_trait]
+pub trait SpotifyGateway: Send + Sync {
+    async fn currently_playing(
+        &self,
+        access_token: &str,
+    ) -> Result<serde_json::Value, SpotifyClientError>;
+
+    async fn recently_played(
+        &self,
+        access_token: &str,
+        limit: u32,
+    ) -> Result<serde_json::Value, SpotifyClientError>;
+}
+
+/// The real [`SpotifyGateway`], backed by [`SpotifyClient`].
+pub struct HttpSpotifyGateway {
+    client: SpotifyClient,
+}
+
+impl HttpSpotifyGateway {
+    pub fn new() -> Self {
+        Self {
+            client: SpotifyClient::new(),
+        }
+    }
+}
+
+impl Default for HttpSpotifyGateway {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SpotifyGateway for HttpSpotifyGateway {
+    async fn currently_playing(
+        &self,
+        access_token: &str,
+    ) -> Result<serde_json::Value, SpotifyClientError> {
+        let response = self
+            .client
+            .send_with_retry(|| {
+                self.client
+                    .http()
+                    .get(format!(
+                        "{}/me/player/currently-playing?market=IN",
+                        SPOTIFY_API_BASE_URL
+                    ))
+                    .header("Authorization", format!("Bearer {}", access_token))
+            })
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::NO_CONTENT => Ok(serde_json::Value::Null),
+            reqwest::StatusCode::OK => Ok(response.json::<serde_json::Value>().await?),
+            status => Err(SpotifyClientError::UnexpectedStatus(status)),
+        }
+    }
+
+    async fn recently_played(
+        &self,
+        access_token: &str,
+        limit: u32,
+    ) -> Result<serde_json::Value, SpotifyClientError> {
+        let response = self
+            .client
+            .send_with_retry(|| {
+                self.client
+                    .http()
+                    .get(format!(
+                        "{}/me/player/recently-played?limit={}&market=IN",
+                        SPOTIFY_API_BASE_URL, limit
+                    ))
+                    .header("Authorization", format!("Bearer {}", access_token))
+            })
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::OK => Ok(response.json::<serde_json::Value>().await?),
+            status => Err(SpotifyClientError::UnexpectedStatus(status)),
+        }
+    }
+}
@@ -1,11 +1,14 @@
 use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
+use tokio::sync::RwLock;
 
-use crate::ENV_CONFIG;
+use crate::{ENV_CONFIG, SpotifyGateway, api::TrackDetails};
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: Arc<SqlitePool>,
+    pub currently_playing_cache: Arc<RwLock<Option<(Instant, TrackDetails)>>>,
+    pub spotify_gateway: Arc<dyn SpotifyGateway>,
 }
 
 pub async fn create_db_pool() -> SqlitePool {